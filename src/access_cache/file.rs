@@ -0,0 +1,54 @@
+use std::{collections::HashMap, error::Error, fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::{top_n_by_score, AccessEntry, AccessStore};
+
+/// The original backend: the whole cache lives in memory and gets dumped
+/// back out to a single TOML file on `flush`.
+#[derive(Serialize, Deserialize)]
+pub struct FileStore {
+    cache: HashMap<PathBuf, AccessEntry>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl FileStore {
+    pub fn load(path: PathBuf) -> Result<Self, Box<dyn Error>> {
+        if !fs::exists(&path)? {
+            return Ok(Self { cache: HashMap::new(), path });
+        }
+        let raw = fs::read_to_string(&path)?;
+        let cache = toml::from_str(&raw)?;
+        return Ok(Self { cache, path });
+    }
+}
+
+impl AccessStore for FileStore {
+    fn get_all_entries(&self) -> HashMap<PathBuf, AccessEntry> {
+        return self.cache.clone();
+    }
+
+    fn register(&mut self, path: PathBuf, entry: AccessEntry) {
+        self.cache.insert(path, entry);
+    }
+
+    fn evict_to(&mut self, capacity: usize) {
+        if self.cache.len() < capacity {
+            return;
+        }
+        self.cache = top_n_by_score(self.cache.clone(), capacity);
+    }
+
+    fn flush(&mut self) {
+        let b = toml::to_string_pretty(&self.cache).expect("[E001]: could not serialize access cache.
+            please report this error.");
+        if !fs::exists(&self.path.parent().expect("[E003] Error whilst trying to create parent cache directory")).unwrap_or(false) {
+            fs::create_dir_all(&self.path.parent().unwrap()).expect("[E002] Could not create cache directory");
+        }
+        if let Err(e) = fs::write(&self.path, b) {
+            eprintln!("Could not write access cache. Recent accesses could not be updated.
+                Error: {}", e)
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use super::{top_n_by_score, AccessEntry, AccessStore};
+
+/// Ephemeral, never persisted. Useful for `SortMode::Alphabetical` (which
+/// doesn't read the cache anyway) and for tests/CI where a `memory://`
+/// `cache_path` avoids touching the filesystem entirely.
+pub struct MemoryStore {
+    cache: HashMap<PathBuf, AccessEntry>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        return Self { cache: HashMap::new() };
+    }
+}
+
+impl AccessStore for MemoryStore {
+    fn get_all_entries(&self) -> HashMap<PathBuf, AccessEntry> {
+        return self.cache.clone();
+    }
+
+    fn register(&mut self, path: PathBuf, entry: AccessEntry) {
+        self.cache.insert(path, entry);
+    }
+
+    fn evict_to(&mut self, capacity: usize) {
+        if self.cache.len() < capacity {
+            return;
+        }
+        self.cache = top_n_by_score(self.cache.clone(), capacity);
+    }
+
+    fn flush(&mut self) {
+        // nothing to do, there's nowhere to write this to.
+    }
+}
@@ -0,0 +1,295 @@
+mod file;
+mod memory;
+mod sled_store;
+
+use std::{cmp::Ordering, collections::HashMap, error::Error, path::PathBuf};
+use chrono::Utc;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::Project;
+
+use file::FileStore;
+use memory::MemoryStore;
+use sled_store::SledStore;
+
+/// A single cache line: how many times a project has been opened, and when
+/// it was last opened.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub struct AccessEntry {
+    pub frequency: u32,
+    pub last_access: i64,
+}
+
+impl AccessEntry {
+    fn first_access(now: i64) -> Self {
+        return Self { frequency: 1, last_access: now };
+    }
+
+    fn bump(&self, now: i64) -> Self {
+        return Self { frequency: self.frequency + 1, last_access: now };
+    }
+}
+
+// Older caches stored a bare `i64` timestamp per project. Accept that shape
+// too, treating it as a single access at that time, so upgrading doesn't
+// blow away everyone's existing cache.
+impl<'de> Deserialize<'de> for AccessEntry {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where D: serde::Deserializer<'de> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Legacy(i64),
+            Full { frequency: u32, last_access: i64 },
+        }
+        return Ok(match Raw::deserialize(deserializer)? {
+            Raw::Legacy(last_access) => AccessEntry { frequency: 1, last_access },
+            Raw::Full { frequency, last_access } => AccessEntry { frequency, last_access },
+        });
+    }
+}
+
+/// Frequency-and-recency score: a project opened often outranks one opened
+/// once recently. Bucketed rather than a smooth decay so scores stay easy
+/// to reason about.
+fn frecency_score(entry: &AccessEntry, now: i64) -> f64 {
+    let age = now - entry.last_access;
+    let weight = if age < 3600 {
+        4.0
+    } else if age < 86400 {
+        2.0
+    } else if age < 604800 {
+        0.5
+    } else {
+        0.25
+    };
+    return entry.frequency as f64 * weight;
+}
+
+/// Keeps the `n` highest-frecency-scoring entries, breaking ties in favour
+/// of the more recently accessed one. Shared by every backend's `evict_to`
+/// so they all honour the same eviction policy.
+pub(crate) fn top_n_by_score(entries: HashMap<PathBuf, AccessEntry>, n: usize) -> HashMap<PathBuf, AccessEntry> {
+    let now = Utc::now().timestamp();
+    return entries.into_iter()
+        .sorted_by(|(_, a), (_, b)| {
+            frecency_score(b, now).partial_cmp(&frecency_score(a, now))
+                .unwrap_or(Ordering::Equal)
+                .then(b.last_access.cmp(&a.last_access))
+        })
+        .take(n)
+        .collect();
+}
+
+/// A pluggable place to persist access history for projects.
+///
+/// Concrete backends are selected by [`AccessCache::from_addr`] based on a
+/// scheme prefix, mirroring how e.g. database connection strings pick a
+/// driver.
+pub trait AccessStore {
+    fn get_all_entries(&self) -> HashMap<PathBuf, AccessEntry>;
+    fn register(&mut self, path: PathBuf, entry: AccessEntry);
+    fn evict_to(&mut self, capacity: usize);
+    fn flush(&mut self);
+}
+
+pub struct AccessCache {
+    store: Box<dyn AccessStore>,
+    capacity: usize,
+}
+
+impl Drop for AccessCache {
+    fn drop(&mut self) {
+        self.store.flush();
+    }
+}
+
+impl AccessCache {
+    /// Selects a backend by parsing a scheme prefix off `addr`, e.g.
+    /// `file:///home/me/.cache/tps/access_cache`, `memory://` or
+    /// `sled:///home/me/.cache/tps/access_cache.sled`. A scheme-less value
+    /// (as every `cache_path` was before backends became pluggable) is
+    /// treated as `file://` for backwards compatibility.
+    pub fn from_addr(addr: &str, capacity: usize) -> Result<Self, Box<dyn Error>> {
+        let store: Box<dyn AccessStore> = if let Some(rest) = addr.strip_prefix("memory://") {
+            let _ = rest; // nothing to point at, this backend is ephemeral.
+            Box::new(MemoryStore::new())
+        } else if let Some(rest) = addr.strip_prefix("sled://") {
+            Box::new(SledStore::open(PathBuf::from(rest))?)
+        } else if let Some(rest) = addr.strip_prefix("file://") {
+            Box::new(FileStore::load(PathBuf::from(rest))?)
+        } else if !addr.contains("://") {
+            Box::new(FileStore::load(PathBuf::from(addr))?)
+        } else {
+            return Err(format!(
+                "[E004] could not parse cache_path '{}': expected one of file://, memory://, sled://",
+                addr
+            ).into());
+        };
+        return Ok(Self { store, capacity });
+    }
+
+    /// An ephemeral, never-persisted cache. Used for sort modes that don't
+    /// read access history but still need somewhere to stash this run's
+    /// accesses.
+    pub fn load_blank(capacity: usize) -> Self {
+        return Self { store: Box::new(MemoryStore::new()), capacity };
+    }
+
+    pub fn register_access(&mut self, project: &Project) {
+        let now = Utc::now().timestamp();
+        let existing = self.store.get_all_entries().get(&project.path).copied();
+        let entry = match existing {
+            Some(entry) => entry.bump(now),
+            None => {
+                self.store.evict_to(self.capacity - 1);
+                AccessEntry::first_access(now)
+            }
+        };
+        self.store.register(project.path.clone(), entry);
+    }
+
+    fn get_entry_for_project(&self, project: &Project) -> AccessEntry {
+        return self.store.get_all_entries()
+            .get(&project.path)
+            .copied()
+            .unwrap_or(AccessEntry { frequency: 0, last_access: 0 });
+    }
+
+    pub fn cmp_projects_by_access_cache_time(&self, a: &Project, b: &Project) -> Ordering {
+        return self.get_entry_for_project(b).last_access.cmp(&self.get_entry_for_project(a).last_access);
+    }
+
+    pub fn cmp_projects_by_frecency(&self, a: &Project, b: &Project) -> Ordering {
+        let now = Utc::now().timestamp();
+        let entry_a = self.get_entry_for_project(a);
+        let entry_b = self.get_entry_for_project(b);
+        return frecency_score(&entry_b, now)
+            .partial_cmp(&frecency_score(&entry_a, now))
+            .unwrap_or(Ordering::Equal)
+            .then(entry_b.last_access.cmp(&entry_a.last_access));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(last_access: i64) -> AccessEntry {
+        return AccessEntry { frequency: 1, last_access };
+    }
+
+    #[test]
+    fn register_access_doesnt_eject_other_cache_lines_when_nonfull() {
+        let mut access_cache = AccessCache::load_blank(10);
+        access_cache.store.register("/my/path/1".into(), entry(0));
+
+        let project = Project::new("/my/path/2".into(), &vec![]);
+        access_cache.register_access(&project);
+
+        assert_eq!(2, access_cache.store.get_all_entries().len());
+    }
+
+    #[test]
+    fn register_access_updates_existing_cache_line() {
+        let mut access_cache = AccessCache::load_blank(10);
+        let target_path: PathBuf = "/my/path/2".into();
+        access_cache.store.register("/my/path/1".into(), entry(0));
+        access_cache.store.register(target_path.clone(), entry(0));
+        access_cache.store.register("/my/path/3".into(), entry(0));
+
+        let project = Project::new("/my/path/2".into(), &vec![]);
+        access_cache.register_access(&project);
+
+        assert_eq!(3, access_cache.store.get_all_entries().len());
+        let updated_cache_line = access_cache.store.get_all_entries().get(&target_path).copied();
+        assert!(updated_cache_line.is_some());
+        assert_ne!(0, updated_cache_line.unwrap().last_access);
+        assert_eq!(2, updated_cache_line.unwrap().frequency);
+    }
+
+    #[test]
+    fn register_access_ejects_oldest_cache_line_over_capacity() {
+        let mut access_cache = AccessCache::load_blank(10);
+        let target_path: PathBuf = "/my/path/0".into();
+        let count: usize = 10;
+        for i in 0..count {
+            access_cache.store.register(format!("/my/path/{}", i).into(), entry(i as i64));
+        }
+
+        assert_eq!(count, access_cache.store.get_all_entries().len());
+
+        let project = Project::new("/a/different/path".into(), &vec![]);
+        access_cache.register_access(&project);
+
+        assert_eq!(count, access_cache.store.get_all_entries().len());
+        let updated_cache_line = access_cache.store.get_all_entries().get(&target_path).copied();
+        assert!(updated_cache_line.is_none());
+    }
+
+    #[test]
+    fn access_entry_deserializes_legacy_bare_timestamp() {
+        let cache: HashMap<PathBuf, AccessEntry> = toml::from_str("\"/p\" = 123").unwrap();
+        assert_eq!(
+            AccessEntry { frequency: 1, last_access: 123 },
+            *cache.get(&PathBuf::from("/p")).unwrap()
+        );
+    }
+
+    #[test]
+    fn frecency_score_buckets_by_age() {
+        let now = 10_000_000;
+        assert_eq!(8.0, frecency_score(&AccessEntry { frequency: 2, last_access: now }, now));
+        assert_eq!(4.0, frecency_score(&AccessEntry { frequency: 2, last_access: now - 3700 }, now));
+        assert_eq!(1.0, frecency_score(&AccessEntry { frequency: 2, last_access: now - 90_000 }, now));
+        assert_eq!(0.5, frecency_score(&AccessEntry { frequency: 2, last_access: now - 700_000 }, now));
+    }
+
+    #[test]
+    fn frecency_score_bucket_boundaries() {
+        let now = 10_000_000;
+        assert_eq!(4.0, frecency_score(&AccessEntry { frequency: 1, last_access: now - 3599 }, now));
+        assert_eq!(2.0, frecency_score(&AccessEntry { frequency: 1, last_access: now - 3600 }, now));
+        assert_eq!(2.0, frecency_score(&AccessEntry { frequency: 1, last_access: now - 86399 }, now));
+        assert_eq!(0.5, frecency_score(&AccessEntry { frequency: 1, last_access: now - 86400 }, now));
+        assert_eq!(0.5, frecency_score(&AccessEntry { frequency: 1, last_access: now - 604799 }, now));
+        assert_eq!(0.25, frecency_score(&AccessEntry { frequency: 1, last_access: now - 604800 }, now));
+    }
+
+    #[test]
+    fn cmp_projects_by_frecency_orders_by_score_descending() {
+        let mut access_cache = AccessCache::load_blank(10);
+        let now = Utc::now().timestamp();
+        access_cache.store.register("/high".into(), AccessEntry { frequency: 10, last_access: now });
+        access_cache.store.register("/low".into(), AccessEntry { frequency: 1, last_access: now });
+
+        let high = Project::new("/high".into(), &vec![]);
+        let low = Project::new("/low".into(), &vec![]);
+
+        assert_eq!(Ordering::Less, access_cache.cmp_projects_by_frecency(&high, &low));
+        assert_eq!(Ordering::Greater, access_cache.cmp_projects_by_frecency(&low, &high));
+    }
+
+    #[test]
+    fn eviction_prefers_high_score_over_recency() {
+        let mut access_cache = AccessCache::load_blank(2);
+        let now = Utc::now().timestamp();
+        access_cache.store.register(
+            "/old_but_frequent".into(),
+            AccessEntry { frequency: 100, last_access: now - 700_000 },
+        );
+        access_cache.store.register(
+            "/recent_but_rare".into(),
+            AccessEntry { frequency: 1, last_access: now },
+        );
+
+        let project = Project::new("/new".into(), &vec![]);
+        access_cache.register_access(&project);
+
+        let entries = access_cache.store.get_all_entries();
+        assert!(entries.contains_key(&PathBuf::from("/old_but_frequent")));
+        assert!(!entries.contains_key(&PathBuf::from("/recent_but_rare")));
+        assert!(entries.contains_key(&PathBuf::from("/new")));
+    }
+}
@@ -0,0 +1,78 @@
+use std::{collections::HashMap, error::Error, path::PathBuf};
+
+use super::{top_n_by_score, AccessEntry, AccessStore};
+
+/// An embedded key-value backend for users with hundreds of projects, where
+/// rewriting a whole TOML file on every access (as `FileStore` does) gets
+/// wasteful. Each entry is a path key mapped to a big-endian-encoded
+/// `AccessEntry`; sled handles its own on-disk persistence, so `flush` only
+/// needs to ask it to fsync.
+pub struct SledStore {
+    db: sled::Db,
+}
+
+impl SledStore {
+    pub fn open(path: PathBuf) -> Result<Self, Box<dyn Error>> {
+        let db = sled::open(path)?;
+        return Ok(Self { db });
+    }
+
+    fn path_key(path: &PathBuf) -> Vec<u8> {
+        return path.to_string_lossy().into_owned().into_bytes();
+    }
+
+    fn encode_entry(entry: &AccessEntry) -> [u8; 12] {
+        let mut buf = [0u8; 12];
+        buf[..4].copy_from_slice(&entry.frequency.to_be_bytes());
+        buf[4..].copy_from_slice(&entry.last_access.to_be_bytes());
+        return buf;
+    }
+
+    fn decode_entry(bytes: &[u8]) -> AccessEntry {
+        let frequency = u32::from_be_bytes(bytes[..4].try_into().expect("[E005] corrupt sled access cache entry"));
+        let last_access = i64::from_be_bytes(bytes[4..].try_into().expect("[E005] corrupt sled access cache entry"));
+        return AccessEntry { frequency, last_access };
+    }
+}
+
+impl AccessStore for SledStore {
+    fn get_all_entries(&self) -> HashMap<PathBuf, AccessEntry> {
+        return self.db.iter()
+            .flatten()
+            .map(|(k, v)| {
+                let path = PathBuf::from(String::from_utf8_lossy(&k).into_owned());
+                (path, Self::decode_entry(v.as_ref()))
+            })
+            .collect();
+    }
+
+    fn register(&mut self, path: PathBuf, entry: AccessEntry) {
+        let key = Self::path_key(&path);
+        if let Err(e) = self.db.insert(key, &Self::encode_entry(&entry)) {
+            eprintln!("Could not write access cache entry. Error: {}", e)
+        }
+    }
+
+    fn evict_to(&mut self, capacity: usize) {
+        if self.db.len() < capacity {
+            return;
+        }
+        let kept = top_n_by_score(self.get_all_entries(), capacity);
+        let doomed = self.get_all_entries()
+            .into_keys()
+            .filter(|path| !kept.contains_key(path))
+            .collect::<Vec<_>>();
+        for path in doomed {
+            if let Err(e) = self.db.remove(Self::path_key(&path)) {
+                eprintln!("Could not evict access cache entry. Error: {}", e)
+            }
+        }
+    }
+
+    fn flush(&mut self) {
+        if let Err(e) = self.db.flush() {
+            eprintln!("Could not flush access cache. Recent accesses could not be updated.
+                Error: {}", e)
+        }
+    }
+}
@@ -16,6 +16,7 @@ pub struct ConfigFileFormat {
 
     sort_mode: Option<String>,
 
+    /// A `file://`, `memory://` or `sled://` address, see `AccessCache::from_addr`.
     cache_path: Option<String>,
 }
 
@@ -23,7 +24,9 @@ pub struct ConfigFileFormat {
 pub enum SortMode {
     #[default]
     Alphabetical,
-    Recent
+    Recent,
+    SessionAge,
+    Frecency
 }
 
 impl From<&str> for SortMode {
@@ -31,9 +34,11 @@ impl From<&str> for SortMode {
         match value.to_lowercase().as_str() {
             "alphabetical" => Self::Alphabetical,
             "recent" => Self::Recent,
+            "sessionage" => Self::SessionAge,
+            "frecency" => Self::Frecency,
             _ => {
                 eprintln!("Could not parse SortMode, please check spelling. Accepted \
-                          Strings: 'alphabetical', 'recent'. Defaulting to alphabetical");
+                          Strings: 'alphabetical', 'recent', 'sessionage', 'frecency'. Defaulting to alphabetical");
                 Self::Alphabetical
             }
         }
@@ -44,7 +49,7 @@ pub struct Config {
     pub projects: Vec<PathBuf>,
     pub skip_current: bool,
     pub sort_mode: SortMode,
-    pub cache_path: PathBuf,
+    pub cache_path: String,
 }
 
 impl Config {
@@ -144,12 +149,11 @@ impl Config {
 
         projects.sort();
 
-        let path: PathBuf = if let Some(raw_path) = conf.cache_path {
-            raw_path.into()
+        let cache_path: String = if let Some(raw_path) = conf.cache_path {
+            raw_path
         } else {
             let cache_dir = base_dirs.cache_dir();
-            cache_dir.join("tps/access_cache")
-
+            format!("file://{}", cache_dir.join("tps/access_cache").display())
         };
 
         let sort_mode = if let Some(mode_str) = conf.sort_mode {
@@ -162,7 +166,7 @@ impl Config {
             projects,
             skip_current,
             sort_mode,
-            cache_path: path
+            cache_path
         });
     }
 }
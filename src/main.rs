@@ -4,16 +4,16 @@ mod access_cache;
 use access_cache::AccessCache;
 use config::{Config, SortMode};
 use itertools::Itertools;
-use std::{env::current_dir, fs, path::PathBuf, process::exit};
+use std::{cmp::Ordering, env::current_dir, fs, path::PathBuf, process::exit};
 use tmux_interface::{tmux::Tmux, list_sessions::ListSessions, NewSession};
 use skim::prelude::*;
-use regex::{Regex, RegexBuilder};
+use chrono::{DateTime, Utc};
 
 #[derive(Debug, Clone)]
 struct Session {
     name: String,
     _window_count: u32,
-    _date_created: String,
+    created: DateTime<Utc>,
     _attached: bool,
 }
 
@@ -55,7 +55,7 @@ impl SkimItem for Project {
     }
 
     fn preview(&self, _context: PreviewContext) -> ItemPreview {
-        let s = fs::read_dir(&self.path)
+        let dir_listing = fs::read_dir(&self.path)
             .unwrap()
             .flat_map(|x| x)
             .map(|x| format!("{}", x
@@ -65,10 +65,42 @@ impl SkimItem for Project {
                     .to_str()
                     .unwrap()))
             .join("\n");
+
+        let s = match &self.session {
+            Some(session) => format!("{}\n\n{}", format_relative_age(session.created), dir_listing),
+            None => dir_listing,
+        };
         return ItemPreview::Text(s);
     }
 }
 
+/// Buckets `Utc::now() - created` into a short human-readable age, e.g.
+/// "created 3d ago". Falls back to an absolute date once it's been over a
+/// week, where "Nd ago" stops being useful at a glance.
+fn format_relative_age(created: DateTime<Utc>) -> String {
+    let age = Utc::now() - created;
+    if age.num_seconds() < 60 {
+        return "created just now".to_string();
+    } else if age.num_seconds() < 3600 {
+        return format!("created {}m ago", age.num_minutes());
+    } else if age.num_seconds() < 86400 {
+        return format!("created {}h ago", age.num_hours());
+    } else if age.num_seconds() < 604800 {
+        return format!("created {}d ago", age.num_days());
+    } else {
+        return format!("created {}", created.format("%Y-%m-%d"));
+    }
+}
+
+fn cmp_projects_by_session_age(a: &Project, b: &Project) -> Ordering {
+    match (&a.session, &b.session) {
+        (Some(a), Some(b)) => b.created.cmp(&a.created),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
 fn attach_from_outside_tmux(_path_name: &str, _session_name: &str, _exists: bool) {
     eprintln!("attaching from outside tmux is currently WIP, please open a tmux session and then call tps.");
     // if exists {
@@ -95,8 +127,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         exit(1);
     };
     let mut access_cache = match config.sort_mode {
-        SortMode::Alphabetical => AccessCache::load_blank(None, 10),
-        SortMode::Recent => AccessCache::load_from_file(config.cache_path, 50)?,
+        SortMode::Alphabetical | SortMode::SessionAge => AccessCache::load_blank(10),
+        SortMode::Recent | SortMode::Frecency => AccessCache::from_addr(&config.cache_path, 50)?,
     };
     let sessions = get_tmux_session_info();
 
@@ -108,6 +140,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     match config.sort_mode {
         SortMode::Alphabetical => (),
         SortMode::Recent => projects.sort_by(|a, b| access_cache.cmp_projects_by_access_cache_time(a, b)),
+        SortMode::SessionAge => projects.sort_by(cmp_projects_by_session_age),
+        SortMode::Frecency => projects.sort_by(|a, b| access_cache.cmp_projects_by_frecency(a, b)),
     }
 
     let skim_opts = SkimOptionsBuilder::default()
@@ -173,29 +207,104 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 }
 
 fn get_tmux_session_info() -> Vec<Session> {
-    let cmd_output = Tmux::with_command(ListSessions::new().build()).output().expect("could not run tmux command").0.stdout;
+    // Machine-readable format instead of parsing tmux's locale-dependent
+    // default listing: gives us session_created as a plain epoch, no
+    // locale-date parsing required.
+    let cmd_output = Tmux::with_command(ListSessions::new()
+            .format("#{session_name}:#{session_created}:#{session_windows}:#{session_attached}")
+            .build())
+        .output()
+        .expect("could not run tmux command")
+        .0
+        .stdout;
     let s = std::str::from_utf8(&cmd_output).expect("could not convert output from utf8.");
 
-    let re_data = RegexBuilder::new(r"(\S*?): (\d+) windows \(created (.*?)\)")
-        .multi_line(true)
-        .build()
-        .unwrap();
-    let re_attached = Regex::new(r"\(attached\)").unwrap();
-
     let mut sessions = Vec::new();
     for line in s.lines() {
-        let hits = re_data.captures(line).unwrap();
-        let name = hits.get(1).unwrap().as_str().to_string();
-        let window_count: u32 = hits.get(2).unwrap().as_str().parse().unwrap();
-        let date_created = hits.get(3).unwrap().as_str().to_string();
-        let attached = re_attached.is_match(line);
+        // session_name can itself contain ':' (tps names sessions after
+        // project paths), but the trailing fields are fixed-shape, so split
+        // from the right and let whatever remains be the name.
+        let mut fields = line.rsplitn(4, ':');
+        let attached = fields.next() == Some("1");
+        let window_count: u32 = fields.next()
+            .and_then(|f| f.parse().ok())
+            .expect("[E008] could not parse session_windows from tmux");
+        let created_epoch: i64 = fields.next()
+            .and_then(|f| f.parse().ok())
+            .expect("[E007] could not parse session_created from tmux");
+        let name = fields.next().expect("[E006] malformed tmux list-sessions output").to_string();
+
+        let created = DateTime::from_timestamp(created_epoch, 0).unwrap_or_else(Utc::now);
 
         sessions.push(Session {
             name,
             _window_count: window_count,
-            _date_created: date_created,
+            created,
             _attached: attached,
         });
     }
     return sessions;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn project_with_session(path: &str, created: DateTime<Utc>) -> Project {
+        let session = Session { name: path.to_string(), _window_count: 0, created, _attached: false };
+        return Project::new(PathBuf::from(path), &[session]);
+    }
+
+    fn project_without_session(path: &str) -> Project {
+        return Project::new(PathBuf::from(path), &[]);
+    }
+
+    #[test]
+    fn format_relative_age_just_now_boundary() {
+        assert_eq!("created just now", format_relative_age(Utc::now() - Duration::seconds(59)));
+        assert_eq!("created 1m ago", format_relative_age(Utc::now() - Duration::seconds(60)));
+    }
+
+    #[test]
+    fn format_relative_age_minutes_hours_boundary() {
+        assert_eq!("created 59m ago", format_relative_age(Utc::now() - Duration::seconds(3599)));
+        assert_eq!("created 1h ago", format_relative_age(Utc::now() - Duration::seconds(3600)));
+    }
+
+    #[test]
+    fn format_relative_age_hours_days_boundary() {
+        assert_eq!("created 23h ago", format_relative_age(Utc::now() - Duration::seconds(86399)));
+        assert_eq!("created 1d ago", format_relative_age(Utc::now() - Duration::seconds(86400)));
+    }
+
+    #[test]
+    fn format_relative_age_falls_back_to_absolute_date_after_a_week() {
+        assert_eq!("created 6d ago", format_relative_age(Utc::now() - Duration::seconds(604799)));
+        let week_old = Utc::now() - Duration::seconds(604800);
+        assert_eq!(format!("created {}", week_old.format("%Y-%m-%d")), format_relative_age(week_old));
+    }
+
+    #[test]
+    fn cmp_projects_by_session_age_orders_sessioned_before_sessionless() {
+        let with_session = project_with_session("/a", Utc::now());
+        let without_session = project_without_session("/b");
+        assert_eq!(Ordering::Less, cmp_projects_by_session_age(&with_session, &without_session));
+        assert_eq!(Ordering::Greater, cmp_projects_by_session_age(&without_session, &with_session));
+    }
+
+    #[test]
+    fn cmp_projects_by_session_age_orders_newer_session_first() {
+        let newer = project_with_session("/a", Utc::now());
+        let older = project_with_session("/b", Utc::now() - Duration::seconds(100));
+        assert_eq!(Ordering::Less, cmp_projects_by_session_age(&newer, &older));
+        assert_eq!(Ordering::Greater, cmp_projects_by_session_age(&older, &newer));
+    }
+
+    #[test]
+    fn cmp_projects_by_session_age_sessionless_projects_are_equal() {
+        let a = project_without_session("/a");
+        let b = project_without_session("/b");
+        assert_eq!(Ordering::Equal, cmp_projects_by_session_age(&a, &b));
+    }
+}